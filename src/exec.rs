@@ -0,0 +1,103 @@
+use std::io;
+use std::process::{Command, Output};
+use std::thread;
+
+use crate::providers::s3::S3Object;
+
+/// A user-supplied command template such as `antivirus-scan {key}`, with
+/// `{bucket}`, `{key}` and `{size}` placeholders substituted per selected
+/// object before being spawned, borrowing the `find -exec` style of piping
+/// selected entries into external tooling.
+///
+/// Nothing in the TUI calls this: there is no multi-select action that runs
+/// a template, and no `ExitStatus` is reported back through the event loop.
+pub struct ExecTemplate {
+    argv: Vec<String>,
+}
+
+impl ExecTemplate {
+    /// Splits `template` on whitespace into an argv; the first word is the
+    /// program, the rest are its arguments. Returns `None` for an empty or
+    /// whitespace-only template, since that has no program to run.
+    pub fn parse(template: &str) -> Option<ExecTemplate> {
+        let argv: Vec<String> = template.split_whitespace().map(String::from).collect();
+        if argv.is_empty() {
+            None
+        } else {
+            Some(ExecTemplate { argv })
+        }
+    }
+
+    /// Runs this template once per object in `objects`, in parallel so one
+    /// slow command doesn't hold up the rest, and returns each object's key
+    /// alongside its process result so the caller can report per-object
+    /// success/failure back through the event loop.
+    pub fn run_on(&self, bucket: &str, objects: &[S3Object]) -> Vec<(String, io::Result<Output>)> {
+        let handles: Vec<(String, thread::JoinHandle<io::Result<Output>>)> = objects
+            .iter()
+            .map(|object| {
+                let key = format!("{}{}", object.prefix, object.name);
+                let argv = self.render(bucket, object, &key);
+                (key, thread::spawn(move || Self::spawn(&argv)))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|(key, handle)| (key, handle.join().expect("exec thread panicked")))
+            .collect()
+    }
+
+    fn render(&self, bucket: &str, object: &S3Object, key: &str) -> Vec<String> {
+        let size = object.size.map(|s| s.to_string()).unwrap_or_default();
+        self.argv
+            .iter()
+            .map(|arg| {
+                arg.replace("{key}", key)
+                    .replace("{bucket}", bucket)
+                    .replace("{size}", &size)
+            })
+            .collect()
+    }
+
+    fn spawn(argv: &[String]) -> io::Result<Output> {
+        let (program, args) = argv
+            .split_first()
+            .expect("Command template must contain at least a program name");
+        Command::new(program).args(args).output()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecTemplate;
+    use crate::providers::Kind;
+    use crate::providers::s3::S3Object;
+    use chrono::Utc;
+
+    fn object(prefix: &str, name: &str, size: i64) -> S3Object {
+        S3Object {
+            name: String::from(name),
+            prefix: String::from(prefix),
+            kind: Kind::File,
+            size: Some(size),
+            last_mod: Utc::now(),
+            storage_class: None,
+            owner: None,
+            tags: None,
+        }
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let template = ExecTemplate::parse("scan {bucket} {key} {size}").unwrap();
+        let obj = object("photos/", "cat.png", 42);
+        let argv = template.render("my-bucket", &obj, "photos/cat.png");
+        assert_eq!(argv, vec!["scan", "my-bucket", "photos/cat.png", "42"]);
+    }
+
+    #[test]
+    fn rejects_empty_template() {
+        assert!(ExecTemplate::parse("   ").is_none());
+    }
+}
@@ -1,7 +1,8 @@
+mod exec;
 pub mod providers;
 mod view;
 
-use providers::s3::S3Provider;
+use providers::{filesystem::TransferProgress, s3::S3Provider};
 use crossterm::{
     event::{self, Event as CEvent, KeyCode, KeyEvent},
     terminal::enable_raw_mode,
@@ -9,7 +10,7 @@ use crossterm::{
 use std::{
     error::Error,
     io::{self, Stdout},
-    sync::{mpsc::{self, Receiver}, Arc},
+    sync::{mpsc::{self, Receiver, Sender}, Arc},
     thread,
     time::{Duration, Instant},
 };
@@ -26,17 +27,20 @@ enum Event<I> {
     Input(I),
     Shutdown,
     Tick,
+    Progress(TransferProgress),
 }
 
 pub struct App {
     main_screen: MainScreen,
     input_channel: Receiver<Event<KeyEvent>>,
+    event_sender: Sender<Event<KeyEvent>>,
 }
 
 impl App {
-    fn spawn_sender() -> Receiver<Event<KeyEvent>> {
+    fn spawn_sender() -> (Sender<Event<KeyEvent>>, Receiver<Event<KeyEvent>>) {
         let (tx, rx) = mpsc::channel();
         let tick_rate = Duration::from_millis(200);
+        let key_tx = tx.clone();
 
         thread::spawn(move || {
             let mut last_tick = Instant::now();
@@ -49,22 +53,42 @@ impl App {
                 if event::poll(timeout).expect("timeout") {
                     if let CEvent::Key(key) = event::read().expect("key") {
                         if key.code == KeyCode::Esc {
-                            tx.send(Event::Shutdown).expect("Can send events");
+                            key_tx.send(Event::Shutdown).expect("Can send events");
                         } else {
-                            tx.send(Event::Input(key)).expect("Can send events");
+                            key_tx.send(Event::Input(key)).expect("Can send events");
                         }
                     }
                 }
 
                 if last_tick.elapsed() >= tick_rate {
-                    if let Ok(_) = tx.send(Event::Tick) {
+                    if let Ok(_) = key_tx.send(Event::Tick) {
                         last_tick = Instant::now();
                     }
                 }
             }
         });
 
-        rx
+        (tx, rx)
+    }
+
+    /// Hands out a sender transfers can clone and move into a spawned task,
+    /// so uploads/downloads can report [`TransferProgress`] back into the
+    /// event loop instead of blocking `handle_key`.
+    ///
+    /// Nothing in this crate calls this: there's no TUI action that kicks
+    /// off a transfer to hand the sender to, and `MainScreen` has no gauge
+    /// to render the resulting `Event::Progress` into.
+    pub fn progress_sender(&self) -> Sender<TransferProgress> {
+        let (progress_tx, progress_rx) = mpsc::channel();
+        let event_tx = self.event_sender.clone();
+        thread::spawn(move || {
+            while let Ok(progress) = progress_rx.recv() {
+                if event_tx.send(Event::Progress(progress)).is_err() {
+                    break;
+                }
+            }
+        });
+        progress_tx
     }
 
     fn capture_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>, Box<dyn Error>> {
@@ -77,12 +101,13 @@ impl App {
     }
 
     pub async fn new(client: Arc<S3Provider>) -> App {
-        let input_channel = App::spawn_sender();
+        let (event_sender, input_channel) = App::spawn_sender();
         let terminal = App::capture_terminal().unwrap();
         let main_screen = MainScreen::new(terminal, client.clone()).await;
         App {
             main_screen,
             input_channel,
+            event_sender,
         }
     }
 
@@ -96,6 +121,9 @@ impl App {
                     break;
                 }
                 Event::Tick => (),
+                // Dropped: MainScreen has no gauge in this tree to render a
+                // TransferProgress into yet (see progress_sender's doc).
+                Event::Progress(_) => (),
             }
         }
         Ok(())
@@ -2,14 +2,19 @@ use std::{
     self,
     borrow::Borrow,
     fs::{self, File},
-    io::{self, BufRead, BufReader, BufWriter, Write},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
     path::{Path, PathBuf},
     pin::Pin,
-    task::Poll,
+    sync::mpsc::Sender,
+    task::{Context, Poll},
 };
 
 use bytes::Bytes;
+use bzip2::read::BzDecoder;
+use flate2::read::GzDecoder;
 use futures::{Stream, StreamExt};
+use tokio::{runtime::Handle, sync::mpsc};
+use xz2::read::XzDecoder;
 
 use super::{BoxedByteStream, Kind};
 
@@ -65,6 +70,168 @@ impl Stream for FileBytesStream {
     }
 }
 
+#[derive(Clone, Copy)]
+pub struct TransferProgress {
+    pub transferred: u64,
+    pub total: Option<u64>,
+}
+
+/// Tallies bytes passing through a stream and reports a [`TransferProgress`]
+/// over `sender` after each chunk.
+pub struct ProgressStream<S> {
+    inner: S,
+    sender: Sender<TransferProgress>,
+    transferred: u64,
+    total: Option<u64>,
+}
+
+impl<S> ProgressStream<S> {
+    pub fn new(inner: S, total: Option<u64>, sender: Sender<TransferProgress>) -> ProgressStream<S> {
+        ProgressStream {
+            inner,
+            sender,
+            transferred: 0,
+            total,
+        }
+    }
+}
+
+impl<S> Stream for ProgressStream<S>
+where
+    S: Stream<Item = Result<Bytes, io::Error>> + Unpin,
+{
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(chunk))) => {
+                self.transferred += chunk.len() as u64;
+                let _ = self.sender.send(TransferProgress {
+                    transferred: self.transferred,
+                    total: self.total,
+                });
+                Poll::Ready(Some(Ok(chunk)))
+            }
+            other => other,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Gzip,
+    Bzip2,
+    Xz,
+}
+
+impl Codec {
+    /// Infers a codec from the key's extension, `None` if not compressed.
+    pub fn from_key(key: &str) -> Option<Codec> {
+        if key.ends_with(".gz") {
+            Some(Codec::Gzip)
+        } else if key.ends_with(".bz2") {
+            Some(Codec::Bzip2)
+        } else if key.ends_with(".xz") {
+            Some(Codec::Xz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Adapts an async [`BoxedByteStream`] into a blocking [`Read`] so the sync
+/// decompression crates can read straight from it. Must be driven from a
+/// `spawn_blocking` thread, since `refill` calls `Handle::block_on`.
+struct BlockingStreamReader {
+    stream: Pin<BoxedByteStream>,
+    handle: Handle,
+    buffer: Bytes,
+}
+
+impl BlockingStreamReader {
+    fn new(stream: Pin<BoxedByteStream>, handle: Handle) -> BlockingStreamReader {
+        BlockingStreamReader {
+            stream,
+            handle,
+            buffer: Bytes::new(),
+        }
+    }
+
+    fn refill(&mut self) -> io::Result<usize> {
+        match self.handle.block_on(self.stream.next()) {
+            Some(chunk) => {
+                self.buffer = chunk?;
+                Ok(self.buffer.len())
+            }
+            None => Ok(0),
+        }
+    }
+}
+
+impl Read for BlockingStreamReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.buffer.is_empty() && self.refill()? == 0 {
+            return Ok(0);
+        }
+        let to_copy = buf.len().min(self.buffer.len());
+        buf[..to_copy].copy_from_slice(&self.buffer[..to_copy]);
+        self.buffer = self.buffer.split_off(to_copy);
+        Ok(to_copy)
+    }
+}
+
+/// Receives decoded chunks from the blocking thread [`decompress`] spawns,
+/// so `poll_next` never blocks the async runtime.
+struct DecodedStream {
+    receiver: mpsc::UnboundedReceiver<Result<Bytes, io::Error>>,
+}
+
+impl Stream for DecodedStream {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// Wraps `stream` in a decoder for `codec` so callers like
+/// `write_file_from_stream` write out the uncompressed bytes. Runs the
+/// decoder on a `spawn_blocking` thread, since `BlockingStreamReader`
+/// bridges back to the async stream via `Handle::block_on`, which panics if
+/// called on a runtime worker thread.
+pub fn decompress(stream: Pin<BoxedByteStream>, codec: Codec, handle: Handle) -> BoxedByteStream {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let reader_handle = handle.clone();
+
+    handle.spawn_blocking(move || {
+        let reader = BlockingStreamReader::new(stream, reader_handle);
+        let mut decoder: Box<dyn Read + Send> = match codec {
+            Codec::Gzip => Box::new(GzDecoder::new(reader)),
+            Codec::Bzip2 => Box::new(BzDecoder::new(reader)),
+            Codec::Xz => Box::new(XzDecoder::new(reader)),
+        };
+
+        loop {
+            let mut chunk = vec![0u8; 64 * 1024];
+            match decoder.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(read) => {
+                    chunk.truncate(read);
+                    if sender.send(Ok(Bytes::from(chunk))).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => {
+                    let _ = sender.send(Err(err));
+                    break;
+                }
+            }
+        }
+    });
+
+    Box::new(DecodedStream { receiver })
+}
+
 pub fn get_files_list(path: &Path) -> Result<Vec<FilesystemObject>, io::Error> {
     if fs::metadata(path)?.is_dir() {
         return Ok(fs::read_dir(path)?
@@ -138,3 +305,36 @@ pub fn remove_file(path: &Path) -> Result<(), io::Error> {
     }
     fs::remove_file(path)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Codec, ProgressStream, TransferProgress};
+    use futures::{stream, StreamExt};
+    use std::sync::mpsc;
+
+    #[test]
+    fn codec_from_key_matches_known_extensions() {
+        assert!(matches!(Codec::from_key("a.gz"), Some(Codec::Gzip)));
+        assert!(matches!(Codec::from_key("a.bz2"), Some(Codec::Bzip2)));
+        assert!(matches!(Codec::from_key("a.xz"), Some(Codec::Xz)));
+        assert!(Codec::from_key("a.txt").is_none());
+    }
+
+    #[tokio::test]
+    async fn progress_stream_tallies_transferred_bytes() {
+        let chunks = vec![
+            Ok(bytes::Bytes::from_static(b"abc")),
+            Ok(bytes::Bytes::from_static(b"de")),
+        ];
+        let (tx, rx) = mpsc::channel();
+        let mut progress = ProgressStream::new(stream::iter(chunks), Some(5), tx);
+
+        while progress.next().await.is_some() {}
+
+        let reports: Vec<TransferProgress> = rx.try_iter().collect();
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].transferred, 3);
+        assert_eq!(reports[1].transferred, 5);
+        assert_eq!(reports[1].total, Some(5));
+    }
+}
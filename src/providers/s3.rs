@@ -1,13 +1,352 @@
+use std::env;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::mpsc::Sender;
+use std::task::{Context, Poll};
+
+use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
-use rusoto_core::{credential::ProfileProvider, ByteStream, HttpClient, Region, RusotoError};
+use futures::{Stream, StreamExt, TryStreamExt};
+use percent_encoding::{AsciiSet, NON_ALPHANUMERIC};
+use rusoto_core::{
+    credential::{
+        AwsCredentials, CredentialsError, DefaultCredentialsProvider, ProfileProvider,
+        ProvideAwsCredentials, StaticProvider,
+    },
+    ByteStream, HttpClient, Region, RusotoError,
+};
 use rusoto_s3::{
+    AbortMultipartUploadError, AbortMultipartUploadRequest, CompleteMultipartUploadError,
+    CompleteMultipartUploadRequest, CompletedMultipartUpload, CompletedPart, CopyObjectError,
+    CopyObjectRequest, CreateMultipartUploadError, CreateMultipartUploadRequest,
     DeleteObjectError, DeleteObjectRequest, GetObjectError, GetObjectOutput, GetObjectRequest,
-    ListObjectsV2Error, ListObjectsV2Request, PutObjectError, PutObjectRequest, S3Client, S3,
+    GetObjectTaggingError, GetObjectTaggingRequest, ListObjectsV2Error, ListObjectsV2Request,
+    PutObjectError, PutObjectRequest, PutObjectTaggingError, PutObjectTaggingRequest, S3Client,
+    Tag, Tagging, UploadPartError, UploadPartRequest, S3,
 };
+use tokio::runtime::Handle;
 
 use crate::view::components::FileEntry;
 
-use super::Kind;
+use super::filesystem::{self, Codec, ProgressStream, TransferProgress};
+use super::{BoxedByteStream, Kind};
+
+/// S3 rejects single-PUT bodies over 5GB anyway, so upload via
+/// `CreateMultipartUpload` at or above this size by default. A failed part
+/// is not retried: the whole multipart upload is aborted. Overridable via [`S3Config::multipart_threshold`].
+const DEFAULT_MULTIPART_THRESHOLD: u64 = 8 * 1024 * 1024;
+/// Smallest part size we'll pick, and the default for objects that don't
+/// need a larger one. Must stay above S3's 5MB-per-part minimum (the last
+/// part is exempt).
+const MULTIPART_MIN_PART_SIZE: u64 = 8 * 1024 * 1024;
+/// S3 rejects a multipart upload with more parts than this, so part size
+/// must scale up for large objects to stay under it.
+const MULTIPART_MAX_PARTS: u64 = 10_000;
+const MULTIPART_CONCURRENCY: usize = 4;
+
+/// Picks the smallest part size that both respects S3's minimum and keeps
+/// the upload under [`MULTIPART_MAX_PARTS`] parts for an object of
+/// `content_length` bytes.
+fn multipart_part_size(content_length: u64) -> usize {
+    let parts_at_min_size = content_length / MULTIPART_MIN_PART_SIZE + 1;
+    if parts_at_min_size <= MULTIPART_MAX_PARTS {
+        MULTIPART_MIN_PART_SIZE as usize
+    } else {
+        (content_length / MULTIPART_MAX_PARTS + 1) as usize
+    }
+}
+
+/// `copy_source` is a `bucket/key` path, so `/` must stay unescaped. ASCII
+/// alphanumerics and `-`, `_`, `.`, `~` also stay as they are; every other
+/// byte of the key gets percent-encoded.
+const COPY_SOURCE_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+#[derive(Debug)]
+pub enum UploadError {
+    Create(RusotoError<CreateMultipartUploadError>),
+    /// `CreateMultipartUpload` responded without an upload id, which a
+    /// non-AWS S3-compatible backend could do even though AWS never does.
+    MissingUploadId,
+    Part(RusotoError<UploadPartError>),
+    Complete(RusotoError<CompleteMultipartUploadError>),
+    Abort(RusotoError<AbortMultipartUploadError>),
+    Put(RusotoError<PutObjectError>),
+    Read(io::Error),
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadError::Create(err) => write!(f, "couldn't create multipart upload: {}", err),
+            UploadError::MissingUploadId => {
+                write!(f, "backend didn't return an upload id for the multipart upload")
+            }
+            UploadError::Part(err) => write!(f, "couldn't upload part: {}", err),
+            UploadError::Complete(err) => write!(f, "couldn't complete multipart upload: {}", err),
+            UploadError::Abort(err) => write!(f, "couldn't abort multipart upload: {}", err),
+            UploadError::Put(err) => write!(f, "couldn't put object: {}", err),
+            UploadError::Read(err) => write!(f, "couldn't read object content: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for UploadError {}
+
+/// Buffers a [`ByteStream`] into fixed-size parts, yielding each as soon as
+/// it's full instead of collecting the whole object into memory first (the
+/// last part may be smaller).
+struct PartStream {
+    content: ByteStream,
+    part_size: usize,
+    current: BytesMut,
+    pending: Option<Bytes>,
+    done: bool,
+}
+
+impl PartStream {
+    fn new(content: ByteStream, part_size: usize) -> PartStream {
+        PartStream {
+            content,
+            part_size,
+            current: BytesMut::with_capacity(part_size),
+            pending: None,
+            done: false,
+        }
+    }
+}
+
+impl Stream for PartStream {
+    type Item = Result<Bytes, io::Error>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.done {
+                return Poll::Ready(None);
+            }
+
+            if let Some(mut pending) = self.pending.take() {
+                let take = (self.part_size - self.current.len()).min(pending.len());
+                self.current.extend_from_slice(&pending.split_to(take));
+                if !pending.is_empty() {
+                    self.pending = Some(pending);
+                }
+                if self.current.len() == self.part_size {
+                    return Poll::Ready(Some(Ok(self.current.split().freeze())));
+                }
+                continue;
+            }
+
+            match Pin::new(&mut self.content).poll_next(cx) {
+                Poll::Ready(Some(Ok(chunk))) => {
+                    self.pending = Some(chunk);
+                }
+                Poll::Ready(Some(Err(err))) => {
+                    self.done = true;
+                    return Poll::Ready(Some(Err(err)));
+                }
+                Poll::Ready(None) => {
+                    self.done = true;
+                    if self.current.is_empty() {
+                        return Poll::Ready(None);
+                    }
+                    return Poll::Ready(Some(Ok(self.current.split().freeze())));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// Error surfaced by [`S3Provider::list_objects`].
+#[derive(Debug)]
+pub enum ListError {
+    Request(RusotoError<ListObjectsV2Error>),
+    /// The backend reported more results (`is_truncated: true`) but didn't
+    /// hand back a token to fetch them. Some S3-compatible backends can do
+    /// this even though AWS never does; looping with `continuation_token`
+    /// reset to `None` would restart the listing from the beginning forever.
+    TruncatedWithoutToken,
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ListError::Request(err) => write!(f, "couldn't list objects: {}", err),
+            ListError::TruncatedWithoutToken => write!(
+                f,
+                "backend reported more results but didn't return a continuation token"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ListError {}
+
+/// Error surfaced by [`S3Provider::move_object`], which is a copy followed
+/// by a delete of the source key.
+#[derive(Debug)]
+pub enum MoveError {
+    Copy(RusotoError<CopyObjectError>),
+    Delete(RusotoError<DeleteObjectError>),
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::Copy(err) => write!(f, "couldn't copy object to destination: {}", err),
+            MoveError::Delete(err) => write!(f, "couldn't delete source object after copy: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+#[derive(Debug)]
+pub enum DownloadError {
+    Get(RusotoError<GetObjectError>),
+    /// `GetObject` responded without a body, which shouldn't happen for a
+    /// successful response but isn't ruled out by the type.
+    MissingBody,
+    Write(io::Error),
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::Get(err) => write!(f, "couldn't get object: {}", err),
+            DownloadError::MissingBody => write!(f, "response didn't contain an object body"),
+            DownloadError::Write(err) => write!(f, "couldn't write object to disk: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+/// How [`S3Provider`] should authenticate against the target bucket.
+pub enum CredentialsMode {
+    /// Use a named profile from `~/.aws/credentials` (`None` for the
+    /// default profile).
+    Profile(Option<String>),
+    /// Use a fixed access key/secret key pair, e.g. loaded from env vars or
+    /// a config file.
+    Static {
+        access_key: String,
+        secret_key: String,
+    },
+    /// Fall back to rusoto's default credential chain (env vars, profile,
+    /// instance metadata, ...).
+    Default,
+}
+
+/// Connection settings for [`S3Provider::new`], letting it target
+/// S3-compatible backends (MinIO, Garage, ...) rather than only AWS.
+pub struct S3Config {
+    pub region: String,
+    pub endpoint: Option<String>,
+    pub credentials: CredentialsMode,
+    /// [`S3Provider::put_object`] switches to a multipart upload at or
+    /// above this many bytes.
+    pub multipart_threshold: u64,
+}
+
+impl Default for S3Config {
+    fn default() -> Self {
+        S3Config {
+            region: String::from("eu-central-1"),
+            endpoint: None,
+            credentials: CredentialsMode::Profile(None),
+            multipart_threshold: DEFAULT_MULTIPART_THRESHOLD,
+        }
+    }
+}
+
+impl S3Config {
+    /// Builds a config from environment variables, for callers that don't
+    /// want to construct a [`S3Config`] by hand: `S3_REGION`, `S3_ENDPOINT`
+    /// and `S3_MULTIPART_THRESHOLD` (all optional, falling back to
+    /// [`S3Config::default`]'s values), then credentials from `AWS_PROFILE`
+    /// if set, else `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY` if both are
+    /// set, else [`CredentialsMode::Default`].
+    pub fn from_env() -> S3Config {
+        let region = env::var("S3_REGION").unwrap_or_else(|_| S3Config::default().region);
+        let endpoint = env::var("S3_ENDPOINT").ok();
+        let multipart_threshold = env::var("S3_MULTIPART_THRESHOLD")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(DEFAULT_MULTIPART_THRESHOLD);
+        let credentials = match env::var("AWS_PROFILE") {
+            Ok(profile) => CredentialsMode::Profile(Some(profile)),
+            Err(_) => match (env::var("AWS_ACCESS_KEY_ID"), env::var("AWS_SECRET_ACCESS_KEY")) {
+                (Ok(access_key), Ok(secret_key)) => CredentialsMode::Static {
+                    access_key,
+                    secret_key,
+                },
+                _ => CredentialsMode::Default,
+            },
+        };
+
+        S3Config {
+            region,
+            endpoint,
+            credentials,
+            multipart_threshold,
+        }
+    }
+}
+
+/// Delegates to whichever credential provider [`CredentialsMode`] selected,
+/// so `S3Client::new_with` can stay generic over a single concrete type.
+enum Credentials {
+    Profile(ProfileProvider),
+    Static(StaticProvider),
+    Default(DefaultCredentialsProvider),
+}
+
+#[async_trait::async_trait]
+impl ProvideAwsCredentials for Credentials {
+    async fn credentials(&self) -> Result<AwsCredentials, CredentialsError> {
+        match self {
+            Credentials::Profile(provider) => provider.credentials().await,
+            Credentials::Static(provider) => provider.credentials().await,
+            Credentials::Default(provider) => provider.credentials().await,
+        }
+    }
+}
+
+impl Credentials {
+    fn from_mode(mode: CredentialsMode) -> Credentials {
+        match mode {
+            CredentialsMode::Profile(Some(profile)) => {
+                let mut provider =
+                    ProfileProvider::new().expect("Couldn't locate the AWS credentials file");
+                provider.set_profile(profile);
+                Credentials::Profile(provider)
+            }
+            CredentialsMode::Profile(None) => {
+                Credentials::Profile(ProfileProvider::new().expect(
+                    "Couldn't locate the AWS credentials file",
+                ))
+            }
+            CredentialsMode::Static {
+                access_key,
+                secret_key,
+            } => Credentials::Static(StaticProvider::new_minimal(access_key, secret_key)),
+            CredentialsMode::Default => Credentials::Default(
+                DefaultCredentialsProvider::new().expect("Couldn't build the default credentials chain"),
+            ),
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct S3Object {
@@ -18,6 +357,9 @@ pub struct S3Object {
     pub last_mod: DateTime<Utc>,
     pub storage_class: Option<String>,
     pub owner: Option<String>,
+    /// Populated on demand via [`S3Provider::get_object_tags`]; `list_objects`
+    /// doesn't return tags, so this is `None` until loaded.
+    pub tags: Option<Vec<Tag>>,
 }
 
 impl FileEntry for S3Object {
@@ -33,64 +375,81 @@ impl FileEntry for S3Object {
 pub struct S3Provider {
     pub bucket_name: String,
     s3_client: S3Client,
+    multipart_threshold: u64,
 }
 
 impl S3Provider {
-    pub async fn new(bucket_name: &str) -> S3Provider {
+    pub async fn new(bucket_name: &str, config: S3Config) -> S3Provider {
+        let region = match config.endpoint {
+            Some(endpoint) => Region::Custom {
+                name: config.region,
+                endpoint,
+            },
+            None => config
+                .region
+                .parse()
+                .expect("Couldn't parse the configured AWS region"),
+        };
+        let credentials = Credentials::from_mode(config.credentials);
+
         S3Provider {
             bucket_name: String::from(bucket_name),
-            s3_client: S3Client::new_with(
-                HttpClient::new().unwrap(),
-                ProfileProvider::new().unwrap(),
-                Region::EuCentral1,
-            ),
+            s3_client: S3Client::new_with(HttpClient::new().unwrap(), credentials, region),
+            multipart_threshold: config.multipart_threshold,
         }
     }
 
-    pub async fn list_objects(
-        &self,
-        prefix: Option<String>,
-    ) -> Result<Vec<S3Object>, RusotoError<ListObjectsV2Error>> {
-        let mut request = ListObjectsV2Request::default();
-        request.bucket = self.bucket_name.clone();
-        request.prefix = prefix.clone();
-        let objects = self.s3_client.list_objects_v2(request);
-        let response = match objects.await?.contents {
-            None => return Ok(Vec::new()),
-            Some(contents) => contents,
-        };
+    pub async fn list_objects(&self, prefix: Option<String>) -> Result<Vec<S3Object>, ListError> {
         let prefix = prefix.unwrap_or(String::new());
-        let result = response
-            .into_iter()
-            .filter(|i| {
-                let key = i.key.clone().unwrap();
-                let (prefix, file_name) = key.split_at(prefix.len());
-                match (prefix, file_name) {
-                    ("", name) => match name.find("/") {
-                        None => true,
-                        Some(i) => i == name.len() - 1,
-                    },
-                    (_, "") => false,
-                    (_, name) => {
-                        let last_char = name.chars().last().unwrap();
-                        let seperator_count = name.matches('/').count();
-                        seperator_count == 0 || (seperator_count == 1 && last_char == '/')
-                    }
-                }
-            })
-            .map(|i| {
-                let key = i.key.clone().unwrap();
-                let (prefix, file_name) = key.split_at(prefix.len());
-                let kind: Kind;
-                if file_name.chars().last().unwrap() == '/' {
-                    kind = Kind::Directory;
-                } else {
-                    kind = Kind::File;
+        let mut result = Vec::new();
+        let mut continuation_token = None;
+
+        loop {
+            let mut request = ListObjectsV2Request::default();
+            request.bucket = self.bucket_name.clone();
+            request.prefix = Some(prefix.clone());
+            request.delimiter = Some(String::from("/"));
+            request.continuation_token = continuation_token;
+
+            let response = self
+                .s3_client
+                .list_objects_v2(request)
+                .await
+                .map_err(ListError::Request)?;
+
+            result.extend(
+                response
+                    .common_prefixes
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|common_prefix| common_prefix.prefix)
+                    .map(|key| {
+                        let (_, dir_name) = key.split_at(prefix.len());
+                        S3Object {
+                            name: String::from(dir_name),
+                            prefix: prefix.clone(),
+                            kind: Kind::Directory,
+                            size: None,
+                            last_mod: Utc::now(),
+                            storage_class: None,
+                            owner: None,
+                            tags: None,
+                        }
+                    }),
+            );
+
+            result.extend(response.contents.unwrap_or_default().into_iter().filter_map(|i| {
+                let key = i.key?;
+                if key == prefix {
+                    // The prefix itself shows up as a zero-length key for
+                    // "directory marker" objects; it isn't a listable entry.
+                    return None;
                 }
-                S3Object {
+                let (_, file_name) = key.split_at(prefix.len());
+                Some(S3Object {
                     name: String::from(file_name),
-                    prefix: String::from(prefix),
-                    kind,
+                    prefix: prefix.clone(),
+                    kind: Kind::File,
                     size: i.size,
                     last_mod: DateTime::parse_from_rfc3339(i.last_modified.unwrap().as_str())
                         .expect("Couldn't parse object's last modification date from string")
@@ -100,9 +459,19 @@ impl S3Provider {
                         Some(own) => own.display_name,
                         None => None,
                     },
-                }
-            })
-            .collect();
+                    tags: None,
+                })
+            }));
+
+            if response.is_truncated != Some(true) {
+                break;
+            }
+            continuation_token = match response.next_continuation_token {
+                Some(token) => Some(token),
+                None => return Err(ListError::TruncatedWithoutToken),
+            };
+        }
+
         Ok(result)
     }
 
@@ -114,6 +483,38 @@ impl S3Provider {
         Ok(object.body.unwrap())
     }
 
+    /// Downloads `object_name` to `dest`, transparently decompressing it if
+    /// its key implies a [`Codec`] and reporting progress over `progress`
+    /// if given. Progress tracks the raw bytes coming off the wire against
+    /// `content_length`, before decompression, so the reported total still
+    /// matches for a compressed key. `handle` drives the decoder's blocking
+    /// thread (see [`filesystem::decompress`]).
+    pub async fn download_object_to_file(
+        &self,
+        object_name: &str,
+        dest: &Path,
+        progress: Option<Sender<TransferProgress>>,
+        handle: Handle,
+    ) -> Result<(), DownloadError> {
+        let object = self.get_object(object_name).await.map_err(DownloadError::Get)?;
+        let total = object.content_length.map(|len| len as u64);
+        let body = object.body.ok_or(DownloadError::MissingBody)?;
+
+        let raw: BoxedByteStream = Box::new(body);
+        let tracked: BoxedByteStream = match progress {
+            Some(sender) => Box::new(ProgressStream::new(raw, total, sender)),
+            None => raw,
+        };
+        let decoded: BoxedByteStream = match Codec::from_key(object_name) {
+            Some(codec) => filesystem::decompress(Box::into_pin(tracked), codec, handle),
+            None => tracked,
+        };
+
+        filesystem::write_file_from_stream(dest, Box::into_pin(decoded))
+            .await
+            .map_err(DownloadError::Write)
+    }
+
     async fn get_object(
         &self,
         object_name: &str,
@@ -135,32 +536,320 @@ impl S3Provider {
         Ok(())
     }
 
+    /// Copies `source_key` from this provider's bucket to `dest_key` in
+    /// `dest_bucket` entirely server-side, without pulling any bytes to the
+    /// client.
+    pub async fn copy_object(
+        &self,
+        source_key: &str,
+        dest_bucket: &str,
+        dest_key: &str,
+    ) -> Result<(), RusotoError<CopyObjectError>> {
+        let copy_source = format!(
+            "{}/{}",
+            self.bucket_name,
+            percent_encoding::utf8_percent_encode(source_key, COPY_SOURCE_ENCODE_SET)
+        );
+
+        let mut request = CopyObjectRequest::default();
+        request.bucket = String::from(dest_bucket);
+        request.key = String::from(dest_key);
+        request.copy_source = copy_source;
+        self.s3_client.copy_object(request).await?;
+        Ok(())
+    }
+
+    /// Moves `source_key` from this provider's bucket to `dest_key` in
+    /// `dest_bucket` by copying server-side and then deleting the source.
+    ///
+    /// No TUI action calls this; there is no rename/move keybinding for it.
+    pub async fn move_object(
+        &self,
+        source_key: &str,
+        dest_bucket: &str,
+        dest_key: &str,
+    ) -> Result<(), MoveError> {
+        self.copy_object(source_key, dest_bucket, dest_key)
+            .await
+            .map_err(MoveError::Copy)?;
+        self.delete_object(source_key).await.map_err(MoveError::Delete)?;
+        Ok(())
+    }
+
+    /// Fetches the key/value tag set currently set on `object_name`.
+    pub async fn get_object_tags(
+        &self,
+        object_name: &str,
+    ) -> Result<Vec<Tag>, RusotoError<GetObjectTaggingError>> {
+        let mut request = GetObjectTaggingRequest::default();
+        request.bucket = self.bucket_name.clone();
+        request.key = String::from(object_name);
+        let response = self.s3_client.get_object_tagging(request).await?;
+        Ok(response.tag_set)
+    }
+
+    /// Replaces `object_name`'s tag set with `tags`.
+    ///
+    /// No TUI view calls this; there is no tag side panel.
+    pub async fn put_object_tags(
+        &self,
+        object_name: &str,
+        tags: Vec<Tag>,
+    ) -> Result<(), RusotoError<PutObjectTaggingError>> {
+        let mut request = PutObjectTaggingRequest::default();
+        request.bucket = self.bucket_name.clone();
+        request.key = String::from(object_name);
+        request.tagging = Tagging { tag_set: tags };
+        self.s3_client.put_object_tagging(request).await?;
+        Ok(())
+    }
+
+    /// Uploads `content`, reporting progress over `progress` if given.
+    /// Dispatches to a multipart upload at or above
+    /// [`S3Config::multipart_threshold`], splitting it into parts sized by
+    /// [`multipart_part_size`] so the upload stays under S3's
+    /// [`MULTIPART_MAX_PARTS`]-part limit regardless of `content_length`.
     pub async fn put_object(
         &self,
         object_name: &str,
         content: ByteStream,
-    ) -> Result<(), RusotoError<PutObjectError>> {
+        content_length: u64,
+        progress: Option<Sender<TransferProgress>>,
+    ) -> Result<(), UploadError> {
+        let content = match progress {
+            Some(sender) => {
+                ByteStream::new(ProgressStream::new(content, Some(content_length), sender))
+            }
+            None => content,
+        };
+
+        if content_length >= self.multipart_threshold {
+            self.multipart_put_object(object_name, content, multipart_part_size(content_length))
+                .await
+        } else {
+            self.simple_put_object(object_name, content).await
+        }
+    }
+
+    async fn simple_put_object(
+        &self,
+        object_name: &str,
+        content: ByteStream,
+    ) -> Result<(), UploadError> {
         let mut request = PutObjectRequest::default();
         request.bucket = self.bucket_name.clone();
         request.key = String::from(object_name);
         request.body = Some(content);
 
-        self.s3_client.put_object(request).await?;
+        self.s3_client
+            .put_object(request)
+            .await
+            .map_err(UploadError::Put)?;
+        Ok(())
+    }
+
+    async fn multipart_put_object(
+        &self,
+        object_name: &str,
+        content: ByteStream,
+        part_size: usize,
+    ) -> Result<(), UploadError> {
+        let mut create_request = CreateMultipartUploadRequest::default();
+        create_request.bucket = self.bucket_name.clone();
+        create_request.key = String::from(object_name);
+        let created = self
+            .s3_client
+            .create_multipart_upload(create_request)
+            .await
+            .map_err(UploadError::Create)?;
+        let upload_id = created.upload_id.ok_or(UploadError::MissingUploadId)?;
+
+        match self.upload_parts(object_name, &upload_id, content, part_size).await {
+            Ok(parts) => self.complete_multipart_upload(object_name, &upload_id, parts).await,
+            Err(err) => {
+                let _ = self.abort_multipart_upload(object_name, &upload_id).await;
+                Err(err)
+            }
+        }
+    }
+
+    /// Uploads parts as [`PartStream`] produces them rather than buffering
+    /// the whole object, keeping memory use bounded to roughly
+    /// `MULTIPART_CONCURRENCY` parts regardless of object size.
+    async fn upload_parts(
+        &self,
+        object_name: &str,
+        upload_id: &str,
+        content: ByteStream,
+        part_size: usize,
+    ) -> Result<Vec<CompletedPart>, UploadError> {
+        let parts = PartStream::new(content, part_size);
+        let mut part_number = 0i64;
+        let mut completed: Vec<CompletedPart> = parts
+            .map(move |part| {
+                part_number += 1;
+                let part_number = part_number;
+                async move {
+                    match part {
+                        Ok(bytes) => self.upload_part(object_name, upload_id, part_number, bytes).await,
+                        Err(err) => Err(UploadError::Read(err)),
+                    }
+                }
+            })
+            .buffer_unordered(MULTIPART_CONCURRENCY)
+            .try_collect()
+            .await?;
+        completed.sort_by_key(|part| part.part_number);
+        Ok(completed)
+    }
+
+    async fn upload_part(
+        &self,
+        object_name: &str,
+        upload_id: &str,
+        part_number: i64,
+        bytes: Bytes,
+    ) -> Result<CompletedPart, UploadError> {
+        let content_md5 = base64::encode(md5::compute(&bytes).0);
+
+        let mut request = UploadPartRequest::default();
+        request.bucket = self.bucket_name.clone();
+        request.key = String::from(object_name);
+        request.upload_id = String::from(upload_id);
+        request.part_number = part_number;
+        request.content_md5 = Some(content_md5);
+        request.body = Some(ByteStream::from(bytes.to_vec()));
+
+        let output = self
+            .s3_client
+            .upload_part(request)
+            .await
+            .map_err(UploadError::Part)?;
+        Ok(CompletedPart {
+            e_tag: output.e_tag,
+            part_number: Some(part_number),
+        })
+    }
+
+    async fn complete_multipart_upload(
+        &self,
+        object_name: &str,
+        upload_id: &str,
+        parts: Vec<CompletedPart>,
+    ) -> Result<(), UploadError> {
+        let mut request = CompleteMultipartUploadRequest::default();
+        request.bucket = self.bucket_name.clone();
+        request.key = String::from(object_name);
+        request.upload_id = String::from(upload_id);
+        request.multipart_upload = Some(CompletedMultipartUpload { parts: Some(parts) });
+
+        self.s3_client
+            .complete_multipart_upload(request)
+            .await
+            .map_err(UploadError::Complete)?;
+        Ok(())
+    }
+
+    async fn abort_multipart_upload(
+        &self,
+        object_name: &str,
+        upload_id: &str,
+    ) -> Result<(), UploadError> {
+        let mut request = AbortMultipartUploadRequest::default();
+        request.bucket = self.bucket_name.clone();
+        request.key = String::from(object_name);
+        request.upload_id = String::from(upload_id);
+
+        self.s3_client
+            .abort_multipart_upload(request)
+            .await
+            .map_err(UploadError::Abort)?;
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::S3Provider;
+    use std::env;
+
+    use super::{multipart_part_size, PartStream, COPY_SOURCE_ENCODE_SET, MULTIPART_MIN_PART_SIZE};
+    use futures::StreamExt;
+    use rusoto_core::ByteStream;
+
+    use super::{CredentialsMode, S3Config, S3Provider};
     const BUCKET_NAME: &str = "s3tui-test-bucket";
 
+    #[test]
+    fn from_env_prefers_profile_over_static_keys() {
+        env::set_var("S3_REGION", "us-west-2");
+        env::set_var("AWS_PROFILE", "work");
+        env::set_var("AWS_ACCESS_KEY_ID", "key");
+        env::set_var("AWS_SECRET_ACCESS_KEY", "secret");
+
+        let config = S3Config::from_env();
+
+        env::remove_var("S3_REGION");
+        env::remove_var("AWS_PROFILE");
+        env::remove_var("AWS_ACCESS_KEY_ID");
+        env::remove_var("AWS_SECRET_ACCESS_KEY");
+
+        assert_eq!(config.region, "us-west-2");
+        assert!(matches!(config.credentials, CredentialsMode::Profile(Some(p)) if p == "work"));
+    }
+
+    #[tokio::test]
+    async fn part_stream_splits_on_size_boundaries() {
+        let part_size = MULTIPART_MIN_PART_SIZE as usize;
+        let total = part_size * 2 + 10;
+        let content = ByteStream::from(vec![0u8; total]);
+        let part_sizes: Vec<usize> = PartStream::new(content, part_size)
+            .map(|part| part.unwrap().len())
+            .collect()
+            .await;
+
+        assert_eq!(part_sizes, vec![part_size, part_size, 10]);
+    }
+
+    #[tokio::test]
+    async fn part_stream_yields_nothing_for_empty_content() {
+        let content = ByteStream::from(Vec::new());
+        let parts: Vec<_> = PartStream::new(content, MULTIPART_MIN_PART_SIZE as usize)
+            .collect()
+            .await;
+        assert!(parts.is_empty());
+    }
+
+    #[test]
+    fn multipart_part_size_stays_under_max_parts_for_huge_objects() {
+        let five_tb = 5 * 1024 * 1024 * 1024 * 1024u64;
+        let part_size = multipart_part_size(five_tb) as u64;
+        assert!((five_tb + part_size - 1) / part_size <= 10_000);
+    }
+
+    #[test]
+    fn multipart_part_size_uses_the_minimum_for_small_objects() {
+        assert_eq!(multipart_part_size(1024), MULTIPART_MIN_PART_SIZE as usize);
+    }
+
+    #[test]
+    fn copy_source_encodes_special_characters_but_not_slashes() {
+        let encoded =
+            percent_encoding::utf8_percent_encode("a dir/file name.txt", COPY_SOURCE_ENCODE_SET)
+                .to_string();
+        assert_eq!(encoded, "a%20dir/file%20name.txt");
+    }
+
     #[tokio::test]
     async fn list_objects_from_bucket() {
-        let cli = S3Provider::new(BUCKET_NAME).await;
+        let cli = S3Provider::new(BUCKET_NAME, S3Config::default()).await;
         let _objects = cli.list_objects(None).await;
     }
 
+    // The continuation-token loop in list_objects isn't covered by a test:
+    // S3Client isn't behind a trait here, and this file's S3Provider tests hit
+    // a live bucket rather than mocking one, so exercising pagination would
+    // need a bucket seeded with >1000 keys.
+
     // #[tokio::test]
     // async fn get_object_from_bucket() {
     //     let cli = Cli::new(BUCKET_NAME).await;
@@ -170,7 +859,7 @@ mod tests {
 
     #[tokio::test]
     async fn remove_item_from_bucket() {
-        let cli = S3Provider::new(BUCKET_NAME).await;
+        let cli = S3Provider::new(BUCKET_NAME, S3Config::default()).await;
         cli.delete_object("delete-object-test.txt").await.unwrap();
     }
 